@@ -0,0 +1,148 @@
+use crate::centrality::{centrality, CentralityMeasure};
+use crate::graph_analysis::construct_graph;
+use crate::predictive_modeling::build_predictive_model;
+use crate::Property;
+use plotters::prelude::*;
+use serde::Serialize;
+use std::error::Error;
+use std::fs;
+use std::time::{Duration, Instant};
+
+/// One timed pipeline stage, recorded at a particular dataset size.
+#[derive(Debug, Clone, Serialize)]
+pub struct Measurement {
+    pub stage: String,
+    pub dataset_size: usize,
+    pub node_count: usize,
+    pub edge_count: usize,
+    pub elapsed_ms: f64,
+}
+
+/// Collects measurements across one or more benchmark runs.
+#[derive(Debug, Default, Serialize)]
+pub struct Measurements {
+    pub records: Vec<Measurement>,
+}
+
+impl Measurements {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&mut self, stage: &str, dataset_size: usize, node_count: usize, edge_count: usize, elapsed: Duration) {
+        self.records.push(Measurement {
+            stage: stage.to_string(),
+            dataset_size,
+            node_count,
+            edge_count,
+            elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+        });
+    }
+
+    /// Prints a plain summary table of every recorded stage to stdout.
+    pub fn print_summary(&self) {
+        println!("{:<16} {:>10} {:>10} {:>10} {:>12}", "stage", "size", "nodes", "edges", "elapsed_ms");
+        for m in &self.records {
+            println!(
+                "{:<16} {:>10} {:>10} {:>10} {:>12.3}",
+                m.stage, m.dataset_size, m.node_count, m.edge_count, m.elapsed_ms
+            );
+        }
+    }
+
+    /// Writes every recorded measurement to `path` as machine-readable JSON, for regression tracking.
+    pub fn write_json(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let json = serde_json::to_string_pretty(&self.records)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// Picks a handful of increasing dataset sizes (25%, 50%, 75%, 100% of `total`) so scaling
+/// curves can be read off a single benchmark run.
+pub fn benchmark_sizes(total: usize) -> Vec<usize> {
+    [total / 4, total / 2, (total * 3) / 4, total]
+        .into_iter()
+        .filter(|&size| size > 0)
+        .collect()
+}
+
+/// Runs the construct/centrality/predict pipeline once per entry in `dataset_sizes`,
+/// truncating `data` to each size, and records each stage's duration. `radius_km` and
+/// `sample_size` are threaded through so the grid-indexed construction and centrality
+/// sampling can be tuned per run and their scaling curves compared side by side.
+pub fn run_benchmarks(
+    data: &[Property],
+    dataset_sizes: &[usize],
+    radius_km: f64,
+    sample_size: Option<usize>,
+) -> Measurements {
+    let mut measurements = Measurements::new();
+
+    for &size in dataset_sizes {
+        let subset = &data[..size.min(data.len())];
+
+        let start = Instant::now();
+        let graph = construct_graph(subset, radius_km, radius_km);
+        measurements.record("construct_graph", size, graph.node_count(), graph.edge_count(), start.elapsed());
+
+        let start = Instant::now();
+        let _ = centrality(&graph, CentralityMeasure::Closeness, sample_size);
+        measurements.record("centrality", size, graph.node_count(), graph.edge_count(), start.elapsed());
+
+        let start = Instant::now();
+        let _ = build_predictive_model(subset);
+        measurements.record("predict", size, graph.node_count(), graph.edge_count(), start.elapsed());
+    }
+
+    measurements
+}
+
+/// Renders a `plotters` line chart of elapsed time vs. dataset size, one line per stage.
+pub fn render_chart(measurements: &Measurements, output_path: &str) -> Result<(), Box<dyn Error>> {
+    let root = BitMapBackend::new(output_path, (1024, 768)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let max_size = measurements.records.iter().map(|m| m.dataset_size).max().unwrap_or(1);
+    let max_elapsed = measurements.records.iter().map(|m| m.elapsed_ms).fold(0.0, f64::max);
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Pipeline runtime vs. dataset size", ("sans-serif", 40))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(0..max_size, 0.0..(max_elapsed * 1.1).max(1.0))?;
+
+    chart.configure_mesh().x_desc("dataset size").y_desc("elapsed (ms)").draw()?;
+
+    // One line per distinct stage, in first-seen order
+    let mut stages: Vec<&str> = Vec::new();
+    for m in &measurements.records {
+        if !stages.contains(&m.stage.as_str()) {
+            stages.push(&m.stage);
+        }
+    }
+
+    for (i, stage) in stages.into_iter().enumerate() {
+        let color = Palette99::pick(i).to_rgba();
+        let mut series: Vec<(usize, f64)> = measurements
+            .records
+            .iter()
+            .filter(|m| m.stage == stage)
+            .map(|m| (m.dataset_size, m.elapsed_ms))
+            .collect();
+        series.sort_by_key(|(size, _)| *size);
+
+        chart
+            .draw_series(LineSeries::new(series, color))?
+            .label(stage)
+            .legend(move |(x, y)| PathElement::new([(x, y), (x + 20, y)], color));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .draw()?;
+    root.present()?;
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use crate::graph_analysis::{undirected_edges, Priority};
+use petgraph::graph::{Graph, NodeIndex};
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Which centrality measure to compute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CentralityMeasure {
+    Degree,
+    Closeness,
+    Betweenness,
+}
+
+/// Computes `measure` for every node in `graph`, returning `(node index, score)` pairs
+/// sorted by descending score. When `sample_size` is `Some`, sources for closeness and
+/// betweenness are drawn via uniform random sampling rather than the first nodes in
+/// insertion order, so large graphs stay tractable without systematically biasing the
+/// result toward whichever properties happen to sit earliest in the dataset.
+pub fn centrality(
+    graph: &Graph<(f64, f64), f64>,
+    measure: CentralityMeasure,
+    sample_size: Option<usize>,
+) -> Vec<(usize, f64)> {
+    match measure {
+        CentralityMeasure::Degree => degree_centrality(graph),
+        CentralityMeasure::Closeness => closeness_centrality(graph, sample_size),
+        CentralityMeasure::Betweenness => betweenness_centrality(graph, sample_size),
+    }
+}
+
+// Picks the sources to run single-source passes from: every node, or a uniform random
+// sample of `sample_size` of them.
+fn sampled_sources(graph: &Graph<(f64, f64), f64>, sample_size: Option<usize>) -> Vec<NodeIndex> {
+    let mut nodes: Vec<NodeIndex> = graph.node_indices().collect();
+    if let Some(size) = sample_size {
+        if size < nodes.len() {
+            nodes.shuffle(&mut thread_rng());
+            nodes.truncate(size);
+        }
+    }
+    nodes
+}
+
+fn degree_centrality(graph: &Graph<(f64, f64), f64>) -> Vec<(usize, f64)> {
+    // `construct_graph` only ever inserts the directed edge low-index -> high-index, so
+    // degree has to be counted over both edge directions, not just the outgoing ones.
+    let mut scores: Vec<(usize, f64)> = graph
+        .node_indices()
+        .map(|node| (node.index(), undirected_edges(graph, node).len() as f64))
+        .collect();
+    scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    scores
+}
+
+// Closeness is `(reachable_count - 1) / sum_of_shortest_path_distances`, found via
+// weighted Dijkstra rather than unweighted BFS, and normalized by the reachable fraction
+// so disconnected graphs aren't inflated by a handful of tightly-linked nodes.
+fn closeness_centrality(graph: &Graph<(f64, f64), f64>, sample_size: Option<usize>) -> Vec<(usize, f64)> {
+    let n = graph.node_count();
+    let sources = sampled_sources(graph, sample_size);
+
+    let mut scores = Vec::new();
+    for source in sources {
+        let distances = dijkstra(graph, source);
+        let reachable = distances.len(); // includes `source` itself at distance 0
+        let sum_distances: f64 = distances.values().sum();
+
+        let closeness = if reachable > 1 && sum_distances > 0.0 {
+            let raw = (reachable - 1) as f64 / sum_distances;
+            raw * (reachable - 1) as f64 / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        scores.push((source.index(), closeness));
+    }
+
+    scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    scores
+}
+
+// Weighted single-source shortest paths, returning distances to every reachable node.
+fn dijkstra(graph: &Graph<(f64, f64), f64>, source: NodeIndex) -> HashMap<NodeIndex, f64> {
+    let mut distances = HashMap::new();
+    let mut visited = HashSet::new();
+    let mut queue = BinaryHeap::new();
+
+    distances.insert(source, 0.0);
+    queue.push((Priority(0.0), source));
+
+    while let Some((Priority(dist), node)) = queue.pop() {
+        if !visited.insert(node) {
+            continue; // already finalized through a shorter path
+        }
+
+        for (neighbor, weight) in undirected_edges(graph, node) {
+            let candidate = dist + weight;
+            if candidate < *distances.get(&neighbor).unwrap_or(&f64::INFINITY) {
+                distances.insert(neighbor, candidate);
+                queue.push((Priority(candidate), neighbor));
+            }
+        }
+    }
+
+    distances
+}
+
+fn betweenness_centrality(graph: &Graph<(f64, f64), f64>, sample_size: Option<usize>) -> Vec<(usize, f64)> {
+    let mut betweenness: HashMap<NodeIndex, f64> =
+        graph.node_indices().map(|node| (node, 0.0)).collect();
+
+    for source in sampled_sources(graph, sample_size) {
+        brandes_accumulate(graph, source, &mut betweenness);
+    }
+
+    let mut scores: Vec<(usize, f64)> = betweenness
+        .into_iter()
+        .map(|(node, score)| (node.index(), score))
+        .collect();
+    scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    scores
+}
+
+// One source's contribution to Brandes' betweenness algorithm: a weighted single-source
+// shortest-path pass that tracks path counts (sigma) and predecessors, followed by a
+// dependency back-propagation pass `delta(v) += (sigma_sv/sigma_sw)(1+delta(w))`.
+fn brandes_accumulate(
+    graph: &Graph<(f64, f64), f64>,
+    source: NodeIndex,
+    betweenness: &mut HashMap<NodeIndex, f64>,
+) {
+    let mut distances: HashMap<NodeIndex, f64> = HashMap::new();
+    let mut sigma: HashMap<NodeIndex, f64> = HashMap::new();
+    let mut predecessors: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+    let mut finalized: HashSet<NodeIndex> = HashSet::new();
+    let mut order = Vec::new(); // nodes in the order they're finalized (non-decreasing distance)
+
+    distances.insert(source, 0.0);
+    sigma.insert(source, 1.0);
+    let mut queue = BinaryHeap::new();
+    queue.push((Priority(0.0), source));
+
+    while let Some((Priority(dist), node)) = queue.pop() {
+        if !finalized.insert(node) {
+            continue;
+        }
+        order.push(node);
+
+        for (neighbor, weight) in undirected_edges(graph, node) {
+            let candidate = dist + weight;
+            let best = *distances.get(&neighbor).unwrap_or(&f64::INFINITY);
+
+            if candidate < best - 1e-12 {
+                distances.insert(neighbor, candidate);
+                sigma.insert(neighbor, sigma[&node]);
+                predecessors.insert(neighbor, vec![node]);
+                queue.push((Priority(candidate), neighbor));
+            } else if (candidate - best).abs() <= 1e-12 {
+                *sigma.entry(neighbor).or_insert(0.0) += sigma[&node];
+                predecessors.entry(neighbor).or_default().push(node);
+            }
+        }
+    }
+
+    let mut delta: HashMap<NodeIndex, f64> = HashMap::new();
+    for &node in order.iter().rev() {
+        let coefficient = (1.0 + *delta.get(&node).unwrap_or(&0.0)) / sigma[&node];
+        for &pred in predecessors.get(&node).unwrap_or(&Vec::new()) {
+            *delta.entry(pred).or_insert(0.0) += sigma[&pred] * coefficient;
+        }
+        if node != source {
+            *betweenness.get_mut(&node).unwrap() += *delta.get(&node).unwrap_or(&0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A complete graph built the way `construct_graph` builds edges: only the directed
+    // edge low-index -> high-index is ever inserted for each pair.
+    fn complete_graph(n: usize) -> Graph<(f64, f64), f64> {
+        let mut graph = Graph::new();
+        let nodes: Vec<NodeIndex> = (0..n).map(|i| graph.add_node((i as f64, 0.0))).collect();
+        for (i, &u) in nodes.iter().enumerate() {
+            for &v in &nodes[i + 1..] {
+                graph.add_edge(u, v, 1.0);
+            }
+        }
+        graph
+    }
+
+    // Every node in a complete graph is topologically equivalent, so every measure should
+    // treat them identically regardless of which node happens to have the lowest index.
+    #[test]
+    fn degree_centrality_is_symmetric_on_a_complete_graph() {
+        let graph = complete_graph(5);
+        let scores = centrality(&graph, CentralityMeasure::Degree, None);
+        assert_eq!(scores.len(), 5);
+        for (_, score) in &scores {
+            assert_eq!(*score, 4.0);
+        }
+    }
+
+    #[test]
+    fn closeness_centrality_is_symmetric_on_a_complete_graph() {
+        let graph = complete_graph(5);
+        let scores = centrality(&graph, CentralityMeasure::Closeness, None);
+        let first_score = scores[0].1;
+        assert!(first_score > 0.0);
+        for (_, score) in &scores {
+            assert!((score - first_score).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn betweenness_centrality_is_zero_on_a_complete_graph() {
+        let graph = complete_graph(5);
+        let scores = centrality(&graph, CentralityMeasure::Betweenness, None);
+        for (_, score) in &scores {
+            assert!(*score < 1e-9);
+        }
+    }
+}
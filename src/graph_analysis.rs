@@ -1,7 +1,21 @@
 use crate::Property;
-use petgraph::visit::EdgeRef; 
+use petgraph::visit::EdgeRef;
 use petgraph::graph::{Graph, NodeIndex};
-use std::collections::HashMap;
+use petgraph::Direction;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Default connection radius (km) used when callers don't care to tune it.
+pub const DEFAULT_RADIUS_KM: f64 = 10.0;
+
+/// The properties `construct_graph` turns into nodes, in node-index order: only those with
+/// both coordinates present. Callers that line up another per-property value (e.g. a
+/// predicted rent) with a node index need to filter through this same function rather than
+/// indexing the original, unfiltered property list, or the two will drift out of alignment
+/// as soon as an earlier property is missing a coordinate.
+pub fn geocoded_properties(data: &[Property]) -> Vec<&Property> {
+    data.iter().filter(|p| p.latitude.is_some() && p.longitude.is_some()).collect()
+}
 
 // Calculates the haversine distance (in km) between two coordinates
 pub fn haversine_distance(coord1: (f64, f64), coord2: (f64, f64)) -> f64 {
@@ -20,25 +34,95 @@ pub fn haversine_distance(coord1: (f64, f64), coord2: (f64, f64)) -> f64 {
     r * c
 }
 
-// Builds the graph using properties as nodes and their spatial relationships as edges
-pub fn construct_graph(data: &[Property]) -> Graph<(f64, f64), f64> {
+// Maps a coordinate onto its grid cell given the cell's angular spans
+fn grid_cell(coord: (f64, f64), delta_lat: f64, delta_lon: f64) -> (i64, i64) {
+    let (lat, lon) = coord;
+    ((lat / delta_lat).floor() as i64, (lon / delta_lon).floor() as i64)
+}
+
+// A degree of longitude covers less ground toward the poles since meridians converge
+// there, so the longitude span (in degrees) needed to cover `delta_lat`'s width in km
+// grows as 1 / cos(lat)
+fn delta_lon_at(lat: f64, delta_lat: f64) -> f64 {
+    delta_lat / lat.to_radians().cos().abs().max(1e-6)
+}
+
+// `construct_graph` only inserts a directed edge once per pair (lower index -> higher
+// index), so `graph.edges(node)` alone only walks half of each spatial connection. This
+// walks both the outgoing and incoming edges at `node` so callers see the graph as the
+// undirected spatial network it actually represents.
+pub(crate) fn undirected_edges(graph: &Graph<(f64, f64), f64>, node: NodeIndex) -> Vec<(NodeIndex, f64)> {
+    let mut edges: Vec<(NodeIndex, f64)> = graph
+        .edges_directed(node, Direction::Outgoing)
+        .map(|edge| (edge.target(), *edge.weight()))
+        .collect();
+    edges.extend(
+        graph
+            .edges_directed(node, Direction::Incoming)
+            .map(|edge| (edge.source(), *edge.weight())),
+    );
+    edges
+}
+
+// Builds the graph using properties as nodes and their spatial relationships as edges.
+//
+// Candidate pairs are narrowed with a grid index instead of comparing every property to
+// every other one: nodes are bucketed into cells at least `cell_size_km` on a side, and
+// each node only needs to test the 3x3 block of cells around its own against the exact
+// haversine distance, rather than the whole dataset. That 3x3 search only covers every
+// node within `radius_km`, though, if a cell spans at least `radius_km`; a smaller cell
+// size would let matches fall outside the ring that gets searched, so it's rejected here
+// rather than silently producing an incomplete graph.
+pub fn construct_graph(data: &[Property], radius_km: f64, cell_size_km: f64) -> Graph<(f64, f64), f64> {
+    assert!(
+        cell_size_km >= radius_km,
+        "cell_size_km ({cell_size_km}) must be >= radius_km ({radius_km}), or the 3x3 neighbor search can miss edges"
+    );
+
     let mut graph = Graph::new();
     let mut nodes = Vec::new();
 
     // Add all valid properties as nodes to the graph
-    for property in data.iter().filter(|p| p.latitude.is_some() && p.longitude.is_some()) {
+    for property in geocoded_properties(data) {
         let coordinates = (property.latitude.unwrap(), property.longitude.unwrap());
         nodes.push(graph.add_node(coordinates));
     }
 
-    // Connect nodes with edges if they are within 10 km
-    for i in 0..nodes.len() {
-        for j in i + 1..nodes.len() {
-            let coord1 = graph[nodes[i]];
-            let coord2 = graph[nodes[j]];
-            let distance = haversine_distance(coord1, coord2);
-            if distance <= 10.0 { // Only connect properties that are close enough
-                graph.add_edge(nodes[i], nodes[j], distance);
+    // Bucket nodes into grid cells so edge construction only has to look at nearby cells
+    let delta_lat = cell_size_km / 111.0;
+    // One longitude span for the whole grid, sized at the highest |latitude| present, so
+    // every node shares the same cell boundaries and no cell is narrower than `cell_size_km`
+    let max_abs_lat = nodes.iter().map(|&node| graph[node].0.abs()).fold(0.0, f64::max);
+    let delta_lon = delta_lon_at(max_abs_lat, delta_lat);
+    let mut grid: HashMap<(i64, i64), Vec<NodeIndex>> = HashMap::new();
+    for &node in &nodes {
+        let coord = graph[node];
+        let cell = grid_cell(coord, delta_lat, delta_lon);
+        grid.entry(cell).or_default().push(node);
+    }
+
+    // For each node, only test candidates in its own cell and the eight neighboring cells
+    for &node_i in &nodes {
+        let coord1 = graph[node_i];
+        let (cell_lat, cell_lon) = grid_cell(coord1, delta_lat, delta_lon);
+
+        for d_lat in -1..=1 {
+            for d_lon in -1..=1 {
+                let neighbor_cell = (cell_lat + d_lat, cell_lon + d_lon);
+                let Some(candidates) = grid.get(&neighbor_cell) else {
+                    continue;
+                };
+                for &node_j in candidates {
+                    // Only examine each unordered pair once, same as the original i < j loop
+                    if node_j.index() <= node_i.index() {
+                        continue;
+                    }
+                    let coord2 = graph[node_j];
+                    let distance = haversine_distance(coord1, coord2);
+                    if distance <= radius_km {
+                        graph.add_edge(node_i, node_j, distance);
+                    }
+                }
             }
         }
     }
@@ -46,54 +130,402 @@ pub fn construct_graph(data: &[Property]) -> Graph<(f64, f64), f64> {
     graph // Return the constructed graph
 }
 
-// Analyzes the centrality of nodes using closeness centrality
-pub fn analyze_centrality(
-    graph: &Graph<(f64, f64), f64>,
-    sample_size: usize,
-) -> Vec<(usize, f64)> {
-    let mut centrality_scores = Vec::new();
+// Assigns each node a community label via modularity-maximizing Louvain clustering.
+//
+// Distance edges are converted to similarity weights with `1 / distance` so spatially
+// tight clusters score higher. The algorithm alternates two phases until the hierarchy
+// stops growing: (1) greedily move each node into whichever neighboring community yields
+// the largest positive modularity gain, and (2) condense each community into a single
+// super-node and repeat phase 1 on the condensed graph. The final labels are the original
+// nodes' communities after unfolding the hierarchy.
+pub fn detect_communities(graph: &Graph<(f64, f64), f64>) -> Vec<usize> {
+    let n = graph.node_count();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // Build a weighted adjacency list, using 1/distance as the similarity weight
+    let mut weights: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+    for edge in graph.edge_indices() {
+        let (u, v) = graph.edge_endpoints(edge).unwrap();
+        let distance = *graph.edge_weight(edge).unwrap();
+        let w = if distance > 0.0 { 1.0 / distance } else { 1.0 };
+        weights[u.index()].push((v.index(), w));
+        weights[v.index()].push((u.index(), w));
+    }
+
+    // `trace[i]` tracks which current-level community original node `i` belongs to, so the
+    // hierarchy can be unfolded back to original nodes once it stops growing.
+    let mut trace: Vec<usize> = (0..n).collect();
+    let mut current = weights;
+
+    loop {
+        let labels = louvain_level(&current);
+        for label in trace.iter_mut() {
+            *label = labels[*label];
+        }
+
+        let num_communities = labels.iter().copied().max().map_or(0, |m| m + 1);
+        if num_communities == current.len() {
+            break; // No further aggregation happened; the hierarchy has converged
+        }
+
+        current = condense_graph(&current, &labels, num_communities);
+    }
+
+    trace
+}
+
+// Runs the Louvain local-moving phase: every node starts in its own community and
+// repeatedly moves to the neighboring community with the largest positive modularity
+// gain until no move improves it. Returns a dense 0..k community label per node.
+fn louvain_level(adjacency: &[Vec<(usize, f64)>]) -> Vec<usize> {
+    let n = adjacency.len();
+    let degree: Vec<f64> = adjacency.iter().map(|edges| edges.iter().map(|(_, w)| w).sum()).collect();
+    let total_weight: f64 = degree.iter().sum(); // 2m, since every edge is recorded from both endpoints
+
+    let mut community: Vec<usize> = (0..n).collect();
+    let mut community_weight = degree.clone(); // total incident weight per community
+
+    if total_weight <= 0.0 {
+        return normalize_labels(&community);
+    }
+
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for node in 0..n {
+            let node_community = community[node];
+
+            // Weight from `node` into each neighboring community (self-loops excluded)
+            let mut neighbor_weight: HashMap<usize, f64> = HashMap::new();
+            for &(neighbor, w) in &adjacency[node] {
+                if neighbor != node {
+                    *neighbor_weight.entry(community[neighbor]).or_insert(0.0) += w;
+                }
+            }
+
+            // Provisionally remove the node from its community before scoring moves
+            community_weight[node_community] -= degree[node];
+
+            let mut best_community = node_community;
+            let mut best_gain = 0.0;
+            for (&candidate, &weight_into) in &neighbor_weight {
+                let gain = weight_into - degree[node] * community_weight[candidate] / total_weight;
+                if gain > best_gain + 1e-12 {
+                    best_gain = gain;
+                    best_community = candidate;
+                }
+            }
+
+            community_weight[best_community] += degree[node];
+            if best_community != node_community {
+                community[node] = best_community;
+                improved = true;
+            }
+        }
+    }
+
+    normalize_labels(&community)
+}
+
+// Condenses communities into super-nodes: inter-community edge weights are summed, and
+// intra-community weight becomes a self-loop on the corresponding super-node.
+fn condense_graph(adjacency: &[Vec<(usize, f64)>], labels: &[usize], num_communities: usize) -> Vec<Vec<(usize, f64)>> {
+    let mut condensed: Vec<HashMap<usize, f64>> = vec![HashMap::new(); num_communities];
+    for (node, edges) in adjacency.iter().enumerate() {
+        let from = labels[node];
+        for &(neighbor, w) in edges {
+            let to = labels[neighbor];
+            *condensed[from].entry(to).or_insert(0.0) += w;
+        }
+    }
+
+    condensed.into_iter().map(|edges| edges.into_iter().collect()).collect()
+}
+
+// Remaps arbitrary community ids into a dense 0..k range
+fn normalize_labels(labels: &[usize]) -> Vec<usize> {
+    let mut remap: HashMap<usize, usize> = HashMap::new();
+    labels
+        .iter()
+        .map(|&c| {
+            let next_id = remap.len();
+            *remap.entry(c).or_insert(next_id)
+        })
+        .collect()
+}
+
+// Ranks non-adjacent node pairs by Adamic-Adar similarity: the sum over their common
+// neighbors `w` of `1 / ln(degree(w))`, skipping neighbors with degree <= 1 (whose
+// contribution would be undefined or infinite). Since the graph already encodes spatial
+// proximity, high-scoring unconnected pairs are properties that sit just beyond the hard
+// distance cutoff in `construct_graph` but are otherwise in the same cluster, which makes
+// them useful for recommending comparable rentals.
+pub fn adamic_adar_scores(graph: &Graph<(f64, f64), f64>, top_k: usize) -> Vec<((usize, usize), f64)> {
     let nodes: Vec<_> = graph.node_indices().collect();
 
-    // Calculate centrality for a subset of nodes (for performance reasons)
-    for node in nodes.iter().take(sample_size) {
-        let total_distance = bfs_total_distance(graph, *node);
-        let centrality = if total_distance > 0.0 {
-            1.0 / total_distance // Invert total distance to calculate closeness
-        } else {
-            0.0
-        };
-        centrality_scores.push((node.index(), centrality));
+    // `undirected_edges` walks both directions, since `construct_graph` only ever inserts
+    // the directed edge low-index -> high-index
+    let neighbors_of = |node: NodeIndex| -> HashSet<NodeIndex> {
+        undirected_edges(graph, node).into_iter().map(|(neighbor, _)| neighbor).collect()
+    };
+    let degree_of = |node: NodeIndex| undirected_edges(graph, node).len();
+
+    let mut scores = Vec::new();
+    for (i, &node_i) in nodes.iter().enumerate() {
+        let neighbors_i = neighbors_of(node_i);
+        for &node_j in &nodes[i + 1..] {
+            if neighbors_i.contains(&node_j) {
+                continue; // only rank pairs that aren't already connected
+            }
+
+            let neighbors_j = neighbors_of(node_j);
+            let score: f64 = neighbors_i
+                .intersection(&neighbors_j)
+                .filter_map(|&w| {
+                    let degree = degree_of(w);
+                    if degree <= 1 {
+                        None
+                    } else {
+                        Some(1.0 / (degree as f64).ln())
+                    }
+                })
+                .sum();
+
+            if score > 0.0 {
+                scores.push(((node_i.index(), node_j.index()), score));
+            }
+        }
     }
 
-    // Sort nodes by their centrality score in descending order
-    centrality_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-    centrality_scores
+    scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    scores.truncate(top_k);
+    scores
 }
 
-// Performs BFS to calculate total distance from the start node
-pub fn bfs_total_distance(graph: &Graph<(f64, f64), f64>, start_node: NodeIndex) -> f64 {
-    let mut visited = HashMap::new();
-    let mut queue = std::collections::VecDeque::new();
-    let mut total_distance = 0.0;
+// Wraps an f64 distance estimate so it can be used as a `BinaryHeap` priority, which
+// requires `Ord`. Distances here are always finite, so NaN ordering isn't a concern.
+// `centrality` reuses this for its own Dijkstra and Brandes passes.
+pub(crate) struct Priority(pub(crate) f64);
 
-    queue.push_back((start_node, 0.0)); // Start BFS with the initial node
+impl PartialEq for Priority {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Priority {}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Priority {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so a max-heap `BinaryHeap` pops the smallest estimate first
+        other.0.partial_cmp(&self.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+// Finds the shortest weighted path between `source` and `target` using A*, with the
+// straight-line haversine distance to the target as an admissible heuristic: no path
+// summed from great-circle edges can ever be shorter than the great-circle distance
+// between its endpoints, so the heuristic never overestimates. Returns the total path
+// distance and the sequence of nodes, or `None` if the target isn't reachable.
+pub fn astar_path(
+    graph: &Graph<(f64, f64), f64>,
+    source: NodeIndex,
+    target: NodeIndex,
+) -> Option<(f64, Vec<NodeIndex>)> {
+    let target_coord = graph[target];
+
+    let mut distances: HashMap<NodeIndex, f64> = HashMap::new();
+    let mut predecessors: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+    let mut visited: HashSet<NodeIndex> = HashSet::new();
+    let mut queue = BinaryHeap::new();
+
+    distances.insert(source, 0.0);
+    queue.push((Priority(haversine_distance(graph[source], target_coord)), source));
+
+    while let Some((_, node)) = queue.pop() {
+        if node == target {
+            let mut path = vec![target];
+            let mut current = target;
+            while let Some(&prev) = predecessors.get(&current) {
+                path.push(prev);
+                current = prev;
+            }
+            path.reverse();
+            return Some((distances[&target], path));
+        }
 
-    while let Some((current_node, distance)) = queue.pop_front() {
-        if visited.contains_key(&current_node) {
-            continue; // Skip nodes we've already visited
+        if !visited.insert(node) {
+            continue; // already finalized through a shorter estimate
         }
 
-        visited.insert(current_node, true);
-        total_distance += distance; // Add the current distance to the total
+        let g_node = distances[&node];
+        for (neighbor, weight) in undirected_edges(graph, node) {
+            let tentative_g = g_node + weight;
+            if tentative_g < *distances.get(&neighbor).unwrap_or(&f64::INFINITY) {
+                distances.insert(neighbor, tentative_g);
+                predecessors.insert(neighbor, node);
+                let h = haversine_distance(graph[neighbor], target_coord);
+                queue.push((Priority(tentative_g + h), neighbor));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A chain 0-1-2-3-4-5 of real, traversable edges. `astar_path` should find a path
+    // regardless of which endpoint is the lower node index, since `construct_graph` only
+    // ever inserts the directed edge low-index -> high-index.
+    fn chain_graph() -> (Graph<(f64, f64), f64>, Vec<NodeIndex>) {
+        let mut graph = Graph::new();
+        let nodes: Vec<NodeIndex> = (0..6)
+            .map(|i| graph.add_node((i as f64 * 0.01, 0.0)))
+            .collect();
+        for i in 0..nodes.len() - 1 {
+            let distance = haversine_distance(graph[nodes[i]], graph[nodes[i + 1]]);
+            graph.add_edge(nodes[i], nodes[i + 1], distance);
+        }
+        (graph, nodes)
+    }
 
-        // Traverse all neighbors of the current node
-        for edge in graph.edges(current_node) {
-            let neighbor = edge.target();
-            if !visited.contains_key(&neighbor) {
-                queue.push_back((neighbor, distance + *edge.weight()));
+    fn add_clique(graph: &mut Graph<(f64, f64), f64>, nodes: &[NodeIndex]) {
+        for (i, &u) in nodes.iter().enumerate() {
+            for &v in &nodes[i + 1..] {
+                let distance = haversine_distance(graph[u], graph[v]).max(0.001);
+                graph.add_edge(u, v, distance);
             }
         }
     }
 
-    total_distance // Return the total distance from the start node
+    #[test]
+    fn detect_communities_groups_disjoint_clusters_together() {
+        let mut graph = Graph::new();
+        let cluster_a: Vec<NodeIndex> = (0..3).map(|i| graph.add_node((i as f64 * 0.001, 0.0))).collect();
+        let cluster_b: Vec<NodeIndex> = (0..3).map(|i| graph.add_node((10.0 + i as f64 * 0.001, 10.0))).collect();
+        add_clique(&mut graph, &cluster_a);
+        add_clique(&mut graph, &cluster_b);
+
+        let communities = detect_communities(&graph);
+
+        assert_eq!(communities[cluster_a[0].index()], communities[cluster_a[1].index()]);
+        assert_eq!(communities[cluster_a[1].index()], communities[cluster_a[2].index()]);
+        assert_eq!(communities[cluster_b[0].index()], communities[cluster_b[1].index()]);
+        assert_ne!(communities[cluster_a[0].index()], communities[cluster_b[0].index()]);
+    }
+
+    // Node 1 is a textbook common neighbor of non-adjacent pair (0, 2): it has degree 2,
+    // so the pair should score `1 / ln(2)`.
+    #[test]
+    fn adamic_adar_scores_finds_common_neighbors_across_the_chain() {
+        let (graph, nodes) = chain_graph();
+
+        let scores = adamic_adar_scores(&graph, 10);
+        assert!(!scores.is_empty());
+
+        let pair_score = scores
+            .iter()
+            .find(|((a, b), _)| (*a, *b) == (nodes[0].index(), nodes[2].index()))
+            .map(|(_, score)| *score);
+        assert!(pair_score.is_some());
+        assert!((pair_score.unwrap() - 1.0 / 2.0_f64.ln()).abs() < 1e-9);
+    }
+
+    // Brute-force O(n^2) reference for `construct_graph`'s edge set: every pair within
+    // `radius_km`, recorded once as (lower index, higher index).
+    fn brute_force_edges(data: &[Property], radius_km: f64) -> HashSet<(usize, usize)> {
+        let coords: Vec<(f64, f64)> = data
+            .iter()
+            .filter(|p| p.latitude.is_some() && p.longitude.is_some())
+            .map(|p| (p.latitude.unwrap(), p.longitude.unwrap()))
+            .collect();
+
+        let mut edges = HashSet::new();
+        for (i, &a) in coords.iter().enumerate() {
+            for (j, &b) in coords.iter().enumerate().skip(i + 1) {
+                if haversine_distance(a, b) <= radius_km {
+                    edges.insert((i, j));
+                }
+            }
+        }
+        edges
+    }
+
+    fn graph_edges(graph: &Graph<(f64, f64), f64>) -> HashSet<(usize, usize)> {
+        graph
+            .edge_indices()
+            .map(|e| {
+                let (u, v) = graph.edge_endpoints(e).unwrap();
+                (u.index(), v.index())
+            })
+            .collect()
+    }
+
+    fn property(lat: f64, lon: f64) -> Property {
+        Property { latitude: Some(lat), longitude: Some(lon), rent_per_sqft: None }
+    }
+
+    #[test]
+    fn construct_graph_matches_brute_force_reference() {
+        let data = vec![
+            property(25.000, 55.000),
+            property(25.005, 55.003),
+            property(25.200, 55.300),
+            property(24.950, 54.900),
+            property(25.050, 55.050),
+            property(26.500, 56.800),
+        ];
+        let radius_km = 10.0;
+
+        let graph = construct_graph(&data, radius_km, radius_km);
+        assert_eq!(graph_edges(&graph), brute_force_edges(&data, radius_km));
+    }
+
+    // Two points in adjacent grid cells, close enough to be within `radius_km` of each
+    // other despite landing on opposite sides of a cell boundary, should still connect.
+    #[test]
+    fn construct_graph_connects_points_straddling_a_grid_cell_boundary() {
+        let radius_km = 10.0;
+        let delta_lat = radius_km / 111.0; // matches `construct_graph`'s cell sizing
+
+        // Straddle the lat=0 cell boundary by a tiny margin on either side
+        let data = vec![property(-0.001 * delta_lat, 0.0), property(0.001 * delta_lat, 0.0)];
+
+        let graph = construct_graph(&data, radius_km, radius_km);
+        assert_eq!(graph_edges(&graph), brute_force_edges(&data, radius_km));
+        assert_eq!(graph.edge_count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be >= radius_km")]
+    fn construct_graph_rejects_cell_size_smaller_than_radius() {
+        let data = vec![property(25.0, 55.0), property(25.01, 55.01)];
+        construct_graph(&data, 10.0, 5.0);
+    }
+
+    #[test]
+    fn astar_path_finds_a_route_in_either_direction() {
+        let (graph, nodes) = chain_graph();
+
+        let forward = astar_path(&graph, nodes[0], nodes[5]);
+        assert!(forward.is_some());
+
+        let backward = astar_path(&graph, nodes[5], nodes[0]);
+        assert!(backward.is_some());
+
+        let (forward_distance, _) = forward.unwrap();
+        let (backward_distance, _) = backward.unwrap();
+        assert!((forward_distance - backward_distance).abs() < 1e-9);
+    }
 }
@@ -1,12 +1,17 @@
+mod benchmarks;
+mod centrality;
 mod graph_analysis;
 mod predictive_modeling;
+mod visualization;
 
-use graph_analysis::{construct_graph, analyze_centrality};
+use benchmarks::{benchmark_sizes, run_benchmarks};
+use centrality::{centrality, CentralityMeasure};
+use graph_analysis::{adamic_adar_scores, astar_path, construct_graph, detect_communities, geocoded_properties, DEFAULT_RADIUS_KM};
 use predictive_modeling::build_predictive_model;
-use plotters::prelude::*;
+use visualization::generate_visualizations;
+use petgraph::graph::NodeIndex;
 use std::error::Error;
 use std::time::Instant;
-use crate::graph_analysis::{bfs_total_distance, haversine_distance};
 
 // This struct holds the key details of a property
 #[derive(Debug)]
@@ -35,23 +40,6 @@ fn process_data(dataset_path: &str) -> Result<Vec<Property>, Box<dyn Error>> {
     Ok(properties)
 }
 
-// Creates a plot to visualize centrality results
-fn generate_visualizations(centrality_results: &[(usize, f64)], _prediction_results: &[f64]) {
-    let root = BitMapBackend::new("output/centrality.png", (1024, 768)).into_drawing_area();
-    root.fill(&WHITE).unwrap();
-
-    let mut chart = ChartBuilder::on(&root)
-        .caption("Top 5 Central Nodes", ("sans-serif", 50))
-        .build_cartesian_2d(0..centrality_results.len(), 0.0..10.0)
-        .unwrap();
-
-    chart
-        .draw_series(centrality_results.iter().map(|(idx, centrality)| {
-            Circle::new((*idx, *centrality), 5, BLUE.filled())
-        }))
-        .unwrap();
-}
-
 fn main() {
     let dataset_path = "dubai_properties.csv";
 
@@ -73,34 +61,87 @@ fn main() {
     // Step 2: Build a graph to represent the spatial relationships between properties
     println!("Constructing spatial graph...");
     let start = Instant::now();
-    let graph = construct_graph(&processed_data[..]);
+    let graph = construct_graph(&processed_data[..], DEFAULT_RADIUS_KM, DEFAULT_RADIUS_KM);
     println!("Graph constructed: {} nodes and {} edges.", graph.node_count(), graph.edge_count());
     println!("Time to construct graph: {:?}", start.elapsed());
 
     // Step 3: Run centrality analysis to find the most connected properties
     println!("Analyzing centrality...");
     let start = Instant::now();
-    let centrality_results = analyze_centrality(&graph, 50); // Limit to a sample of 50 nodes
+    let degree_results = centrality(&graph, CentralityMeasure::Degree, None);
+    let centrality_results = centrality(&graph, CentralityMeasure::Betweenness, Some(50)); // Sample 50 nodes
+    let top_degree = degree_results.iter().take(5).collect::<Vec<_>>(); // Get top 5 by raw connectivity
     let top_centrality = centrality_results.iter().take(5).collect::<Vec<_>>(); // Get top 5 nodes
     println!("Time to analyze centrality: {:?}", start.elapsed());
+    println!("Top 5 Nodes by Degree: {:?}", top_degree);
     println!("Top 5 Central Nodes: {:?}", top_centrality);
 
+    // Step 3.5: Cluster properties into spatial neighborhoods via community detection
+    println!("Detecting communities...");
+    let start = Instant::now();
+    let communities = detect_communities(&graph);
+    println!("Time to detect communities: {:?}", start.elapsed());
+
+    // Step 3.55: Flag comparable-but-unconnected listings via Adamic-Adar link prediction
+    println!("Scoring comparable listings...");
+    let start = Instant::now();
+    let comparable_listings = adamic_adar_scores(&graph, 5);
+    println!("Top comparable (unconnected) property pairs: {:?}", comparable_listings);
+    println!("Time to score comparable listings: {:?}", start.elapsed());
+
+    // Step 3.6: Find an example weighted route between two properties via A*
+    if graph.node_count() >= 2 {
+        let start = Instant::now();
+        let source = NodeIndex::new(0);
+        let target = NodeIndex::new(graph.node_count() - 1);
+        match astar_path(&graph, source, target) {
+            Some((distance, path)) => println!(
+                "Shortest route between property 0 and property {}: {:.2} km across {} hops",
+                target.index(),
+                distance,
+                path.len().saturating_sub(1)
+            ),
+            None => println!("No route found between property 0 and property {}", target.index()),
+        }
+        println!("Time to compute example route: {:?}", start.elapsed());
+    }
+
     // Step 4: Predict demand using a lightweight predictive model
     println!("Building predictive model...");
     let start = Instant::now();
-    let prediction_results = build_predictive_model(&processed_data);
+    // Predictions are indexed by graph node index in Step 5, so they have to come from the
+    // same coordinate-filtered subset `construct_graph` turned into nodes, not the full
+    // unfiltered dataset, or they'll drift out of alignment as soon as any earlier property
+    // is missing a coordinate.
+    let prediction_results = build_predictive_model(geocoded_properties(&processed_data));
     println!("Time to build predictive model: {:?}", start.elapsed());
     println!(
         "Prediction completed. Example prediction for the first property: {:.2}",
         prediction_results.first().unwrap_or(&0.0)
     );
 
-    // Step 5: Visualize the centrality results in a graph
+    // Step 5: Visualize the property map, colored by community and sized by predicted rent
     println!("Generating visualizations...");
     let start = Instant::now();
-    generate_visualizations(&centrality_results, &prediction_results);
+    if let Err(e) = generate_visualizations(&graph, &communities, &centrality_results, &prediction_results, "output/centrality.png") {
+        eprintln!("Error generating visualizations: {}", e);
+    }
     println!("Time to generate visualizations: {:?}", start.elapsed());
 
+    // Step 6: Benchmark each stage across a few dataset sizes so scaling is visible
+    println!("Running benchmarks...");
+    let start = Instant::now();
+    let sizes = benchmark_sizes(processed_data.len());
+    let measurements = run_benchmarks(&processed_data, &sizes, DEFAULT_RADIUS_KM, Some(50));
+    measurements.print_summary();
+    if let Err(e) = measurements.write_json("output/benchmarks.json") {
+        eprintln!("Error writing benchmark results: {}", e);
+    }
+    if let Err(e) = benchmarks::render_chart(&measurements, "output/benchmarks.png") {
+        eprintln!("Error rendering benchmark chart: {}", e);
+    }
+    println!("Time to run benchmarks: {:?}", start.elapsed());
+
     // Print the summary of all results
     println!("\nSummary of Analysis:");
     println!("---------------------");
@@ -0,0 +1,10 @@
+use crate::Property;
+
+// Predicts rent demand using a simple multiplier (placeholder model). Takes anything
+// iterable over `&Property` so callers can pass either the full dataset or a filtered
+// subset (e.g. `graph_analysis::geocoded_properties`) without an intermediate collection.
+pub fn build_predictive_model<'a>(data: impl IntoIterator<Item = &'a Property>) -> Vec<f64> {
+    data.into_iter()
+        .map(|p| p.rent_per_sqft.unwrap_or(0.0) * 1.1) // Example prediction
+        .collect()
+}
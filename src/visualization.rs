@@ -1,20 +1,95 @@
+use petgraph::graph::Graph;
+use petgraph::visit::EdgeRef;
 use plotters::prelude::*;
+use std::collections::HashMap;
+use std::error::Error;
 
+/// Plots properties on a true lat/lon map instead of an index scatter. The left panel
+/// colors each point by its community label (or, if no communities are given, by a
+/// centrality heat gradient) and sizes it by predicted rent; the right panel overlays the
+/// constructed edges as line segments so spatial clusters are visible by connectivity
+/// rather than color alone.
 pub fn generate_visualizations(
+    graph: &Graph<(f64, f64), f64>,
+    communities: &[usize],
     centrality_results: &[(usize, f64)],
-    _prediction_results: &[f64],
-) {
-    let root = BitMapBackend::new("output/centrality.png", (1024, 768)).into_drawing_area();
-    root.fill(&WHITE).unwrap();
-
-    let mut chart = ChartBuilder::on(&root)
-        .caption("Top 5 Central Nodes", ("sans-serif", 50))
-        .build_cartesian_2d(0..centrality_results.len(), 0.0..10.0)
-        .unwrap();
-
-    chart
-        .draw_series(centrality_results.iter().map(|(idx, centrality)| {
-            Circle::new((*idx, *centrality), 5, BLUE.filled())
-        }))
-        .unwrap();
+    predicted_rents: &[f64],
+    output_path: &str,
+) -> Result<(), Box<dyn Error>> {
+    let root = BitMapBackend::new(output_path, (1600, 800)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let (left, right) = root.split_horizontally(800);
+
+    // Plot as (x = longitude, y = latitude) so the chart reads as an actual map
+    let coords: Vec<(f64, f64)> = graph.node_indices().map(|n| {
+        let (lat, lon) = graph[n];
+        (lon, lat)
+    }).collect();
+
+    if coords.is_empty() {
+        return Ok(());
+    }
+
+    let (min_lon, max_lon) = bounds(coords.iter().map(|(lon, _)| *lon));
+    let (min_lat, max_lat) = bounds(coords.iter().map(|(_, lat)| *lat));
+
+    let max_centrality = centrality_results.iter().map(|(_, c)| *c).fold(0.0, f64::max);
+    let centrality_by_node: HashMap<usize, f64> = centrality_results.iter().cloned().collect();
+    let max_rent = predicted_rents.iter().cloned().fold(0.0, f64::max);
+
+    let mut left_chart = ChartBuilder::on(&left)
+        .caption("Property clusters by community", ("sans-serif", 30))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(min_lon..max_lon, min_lat..max_lat)?;
+    left_chart.configure_mesh().x_desc("longitude").y_desc("latitude").draw()?;
+
+    left_chart.draw_series(coords.iter().enumerate().map(|(idx, &(lon, lat))| {
+        let color = if idx < communities.len() {
+            Palette99::pick(communities[idx]).to_rgba()
+        } else {
+            heat_color(*centrality_by_node.get(&idx).unwrap_or(&0.0), max_centrality)
+        };
+        let radius = point_radius(predicted_rents.get(idx).copied().unwrap_or(0.0), max_rent);
+        Circle::new((lon, lat), radius, color.filled())
+    }))?;
+
+    let mut right_chart = ChartBuilder::on(&right)
+        .caption("Constructed edges", ("sans-serif", 30))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(min_lon..max_lon, min_lat..max_lat)?;
+    right_chart.configure_mesh().x_desc("longitude").y_desc("latitude").draw()?;
+
+    right_chart.draw_series(graph.edge_references().map(|edge| {
+        let (lat1, lon1) = graph[edge.source()];
+        let (lat2, lon2) = graph[edge.target()];
+        PathElement::new(vec![(lon1, lat1), (lon2, lat2)], BLACK.mix(0.15))
+    }))?;
+    right_chart.draw_series(
+        coords.iter().map(|&(lon, lat)| Circle::new((lon, lat), 2, BLUE.filled())),
+    )?;
+
+    root.present()?;
+    Ok(())
+}
+
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| (min.min(v), max.max(v)))
+}
+
+// Interpolates a blue-to-red gradient for a centrality value relative to the observed max
+fn heat_color(value: f64, max_value: f64) -> RGBAColor {
+    let t = if max_value > 0.0 { (value / max_value).clamp(0.0, 1.0) } else { 0.0 };
+    RGBColor((t * 255.0) as u8, 0, ((1.0 - t) * 255.0) as u8).to_rgba()
+}
+
+// Scales a predicted rent into a point radius between 3 and 12 pixels
+fn point_radius(rent: f64, max_rent: f64) -> i32 {
+    if max_rent <= 0.0 {
+        return 4;
+    }
+    3 + ((rent / max_rent).clamp(0.0, 1.0) * 9.0) as i32
 }